@@ -1,19 +1,206 @@
-//! A set of functions that simplifies comparison between two JSON objects. 
-use serde_json::Value;
+//! A set of functions that simplifies comparison between two JSON objects.
+use serde_json::{Number, Value};
 
-/// A compare function between two JSON Values. Compare returns a boolean true 
-/// or false if the Valus are equal. Takes the `exp` argument as the base of 
+/// The reason a single node of `exp` failed to match the corresponding node of `got`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MismatchKind {
+    /// The two values are of a comparable type but are not equal, or `got` fails a relational
+    /// operator object in `exp` (such as `{"$gt": 100}` or `{"$ne": ...}`).
+    ValueDiffers,
+    /// A key present in an expected object is missing from `got`.
+    MissingKey,
+    /// The two values are not of the same JSON type (e.g. an array compared against an object).
+    TypeMismatch,
+    /// An expected array and the corresponding got array are different lengths.
+    ArrayLengthMismatch,
+    /// `got` contains a key, not present in `exp`, that is not allowed under the active options
+    /// (see [`Options::exact`]).
+    ExtraKey,
+}
+
+impl std::fmt::Display for MismatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            MismatchKind::ValueDiffers => "values differ",
+            MismatchKind::MissingKey => "missing key",
+            MismatchKind::TypeMismatch => "type mismatch",
+            MismatchKind::ArrayLengthMismatch => "array length mismatch",
+            MismatchKind::ExtraKey => "unexpected key",
+        };
+        f.write_str(description)
+    }
+}
+
+/// Describes the first point of disagreement found while comparing `exp` against `got`.
+///
+/// `path` is a JSON pointer (RFC 6901) to the offending node, e.g. `/baz/1/second`. `exp` and
+/// `got` are the values found at that path; for a `MissingKey` mismatch, `got` is the object
+/// that was missing the key; for an `ExtraKey` mismatch, `exp` is the expected object that
+/// lacks the key; for a failed operator object such as `{"$gt": 100}`, `exp` is the operand.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch<'a> {
+    pub path: String,
+    pub kind: MismatchKind,
+    pub exp: &'a Value,
+    pub got: &'a Value,
+}
+
+fn encode_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Options controlling the matching behavior of [`cmp_expected_with`] and
+/// [`find_mismatch_with`]. The default value reproduces the behavior of
+/// [`cmp_expected`] / [`find_mismatch`].
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// When `true`, certain literals in `exp` are treated as wildcards rather than literal
+    /// values: an expected string of `"{...}"` matches any `got` value, and the token `[..]`
+    /// inside an expected string matches any run of characters in the corresponding `got`
+    /// string.
+    pub wildcards: bool,
+    /// When `true`, arrays are compared as multisets instead of positionally: every element of
+    /// `exp` must have a distinct matching element somewhere in `got` (order does not matter).
+    /// Array lengths must still match.
+    pub ignore_array_order: bool,
+    /// When `true`, objects in `got` may not contain keys absent from `exp`: this upgrades the
+    /// usual subset semantics to exact structural equality.
+    pub exact: bool,
+}
+
+/// Whether every element of `exp` can be matched to a distinct element of `got`, using
+/// `cmp_expected_with` as the per-pair match relation. Since that relation is asymmetric (an
+/// expected element may be a partial match for several got elements, and vice versa), this is a
+/// bipartite matching problem rather than a sort-and-compare one; it's solved with the standard
+/// augmenting-path (Kuhn's) algorithm.
+fn arrays_match_unordered(exp: &[Value], got: &[Value], opts: &Options) -> bool {
+    let adjacency: Vec<Vec<usize>> = exp.iter().map(|e| {
+        got.iter().enumerate().filter(|(_, g)| cmp_expected_with(g, e, opts)).map(|(j, _)| j).collect()
+    }).collect();
+
+    let mut matched_to: Vec<Option<usize>> = vec![None; got.len()];
+    for e_idx in 0..exp.len() {
+        let mut visited = vec![false; got.len()];
+        if !try_augment(e_idx, &adjacency, &mut visited, &mut matched_to) {
+            return false
+        }
+    }
+    true
+}
+
+fn try_augment(e_idx: usize, adjacency: &[Vec<usize>], visited: &mut [bool], matched_to: &mut [Option<usize>]) -> bool {
+    for &g_idx in &adjacency[e_idx] {
+        if visited[g_idx] {
+            continue
+        }
+        visited[g_idx] = true;
+        let free = match matched_to[g_idx] {
+            None => true,
+            Some(other) => try_augment(other, adjacency, visited, matched_to)
+        };
+        if free {
+            matched_to[g_idx] = Some(e_idx);
+            return true
+        }
+    }
+    false
+}
+
+/// The relational operator keys recognized in an `exp` object. An object with exactly one of
+/// these keys is interpreted as an operator applied to the corresponding `got` value, rather
+/// than as a literal object to match against `got`.
+const OPERATOR_KEYS: [&str; 5] = ["$gt", "$lt", "$gte", "$lte", "$ne"];
+
+/// If `obj` is a sentinel operator object (its only key is one of [`OPERATOR_KEYS`]), returns
+/// the operator and its operand.
+fn as_operator(obj: &serde_json::Map<String, Value>) -> Option<(&str, &Value)> {
+    if obj.len() != 1 {
+        return None
+    }
+    OPERATOR_KEYS.iter().find_map(|&op| obj.get(op).map(|operand| (op, operand)))
+}
+
+/// Orders `got` relative to `exp` for the `$gt`/`$gte`/`$lt`/`$lte` operators. Numbers are ordered
+/// by value; strings are ordered lexicographically. Any other pairing is not ordered.
+fn partial_order(exp: &Value, got: &Value) -> Option<std::cmp::Ordering> {
+    match (got, exp) {
+        (Value::Number(g), Value::Number(e)) => match (as_wide_int(g), as_wide_int(e)) {
+            (Some(g), Some(e)) => Some(g.cmp(&e)),
+            _ => g.as_f64()?.partial_cmp(&e.as_f64()?)
+        },
+        (Value::String(g), Value::String(e)) => Some(g.cmp(e)),
+        _ => None
+    }
+}
+
+fn operator_matches(op: &str, operand: &Value, got: &Value, opts: &Options, path: &str) -> bool {
+    use std::cmp::Ordering::*;
+    match op {
+        "$ne" => find_mismatch_at(got, operand, opts, path.to_string()).is_some(),
+        "$gt" => partial_order(operand, got) == Some(Greater),
+        "$gte" => matches!(partial_order(operand, got), Some(Greater) | Some(Equal)),
+        "$lt" => partial_order(operand, got) == Some(Less),
+        "$lte" => matches!(partial_order(operand, got), Some(Less) | Some(Equal)),
+        _ => unreachable!("as_operator only returns keys from OPERATOR_KEYS")
+    }
+}
+
+/// Matches an expected wildcard string such as `"id-[..]"` against a got string, where `[..]`
+/// stands in for any run of characters.
+fn wildcard_str_matches(pattern: &str, value: &str) -> bool {
+    let mut segments = pattern.split("[..]");
+    let first = segments.next().unwrap_or("");
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false
+    };
+    let segments: Vec<&str> = segments.collect();
+    for (i, segment) in segments.iter().enumerate() {
+        if i == segments.len() - 1 {
+            return rest.ends_with(segment)
+        }
+        match rest.find(segment) {
+            Some(idx) => rest = &rest[idx + segment.len()..],
+            None => return false
+        }
+    }
+    true
+}
+
+/// Widens an integer `Number` (either representation) to `i128`, so that integers which don't
+/// fit in `i64` (large `u64` values) can still be compared exactly.
+fn as_wide_int(n: &Number) -> Option<i128> {
+    n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from))
+}
+
+/// Compares two JSON numbers by mathematical value rather than by representation, so that
+/// `json!(1)` and `json!(1.0)` are considered equal. Integers are compared exactly via `i128`;
+/// if either side carries a fractional/floating representation, both are compared as `f64`
+/// (with NaN never equal to anything, matching IEEE 754).
+fn numbers_equal(exp: &Number, got: &Number) -> bool {
+    if !exp.is_f64() && !got.is_f64() {
+        if let (Some(e), Some(g)) = (as_wide_int(exp), as_wide_int(got)) {
+            return e == g
+        }
+    }
+    match (exp.as_f64(), got.as_f64()) {
+        (Some(e), Some(g)) => !e.is_nan() && !g.is_nan() && e == g,
+        _ => false
+    }
+}
+
+/// A compare function between two JSON Values. Compare returns a boolean true
+/// or false if the Valus are equal. Takes the `exp` argument as the base of
 /// the comparison.
-/// 
-/// This function compares to see that all values in the `exp` argument are also 
+///
+/// This function compares to see that all values in the `exp` argument are also
 /// present in the `got` argument. It will still return true if `got` has extra
 /// object elements not present in `exp`
-/// 
+///
 /// ```rust
-/// 
+///
 /// use serde_json::json;
 /// use cmp_json::cmp_expected;
-/// 
+///
 /// let exp = json!{{
 ///     "some": "value"
 /// }};
@@ -24,43 +211,212 @@ use serde_json::Value;
 /// let got_different = json!{{
 ///     "some": 2
 /// }};
-/// 
+///
 /// assert!(cmp_expected(&got_extra, &exp));
 /// assert!(cmp_expected(&got_different, &exp) == false);
 /// ```
-/// 
+///
 pub fn cmp_expected(got: &Value, exp: &Value) -> bool {
+    find_mismatch(got, exp).is_none()
+}
+
+/// Like [`cmp_expected`], but matched according to `opts` instead of the default behavior.
+///
+/// ```rust
+///
+/// use serde_json::json;
+/// use cmp_json::{cmp_expected_with, Options};
+///
+/// let exp = json!{{
+///     "id": "id-[..]",
+///     "payload": "{...}"
+/// }};
+/// let got = json!{{
+///     "id": "id-9f3a",
+///     "payload": {"anything": "at all"}
+/// }};
+///
+/// let opts = Options { wildcards: true, ..Options::default() };
+/// assert!(cmp_expected_with(&got, &exp, &opts));
+/// ```
+///
+pub fn cmp_expected_with(got: &Value, exp: &Value, opts: &Options) -> bool {
+    find_mismatch_with(got, exp, opts).is_none()
+}
+
+/// Renders a [`Mismatch`] as a human-readable message: the JSON pointer path it occurred at,
+/// the reason, and a pretty-printed side-by-side of the expected and actual values. Used by
+/// [`assert_json_include!`] and [`assert_json_eq!`] to produce panic messages.
+pub fn format_mismatch(mismatch: &Mismatch) -> String {
+    let pretty = |v: &Value| serde_json::to_string_pretty(v).unwrap_or_else(|_| format!("{:?}", v));
+    let (exp, got) = (pretty(mismatch.exp), pretty(mismatch.got));
+    let exp_lines: Vec<&str> = exp.lines().collect();
+    let got_lines: Vec<&str> = got.lines().collect();
+    let width = exp_lines.iter().map(|l| l.chars().count()).chain(std::iter::once("expected:".len())).max().unwrap_or(0);
+    let mut message = format!(
+        "json mismatch at `{}`: {}\n  {:<width$} | got:",
+        if mismatch.path.is_empty() { "/" } else { &mismatch.path },
+        mismatch.kind,
+        "expected:",
+        width = width,
+    );
+    for i in 0..exp_lines.len().max(got_lines.len()) {
+        let (e, g) = (exp_lines.get(i).copied().unwrap_or(""), got_lines.get(i).copied().unwrap_or(""));
+        message.push_str(&format!("\n  {:<width$} | {}", e, g, width = width));
+    }
+    message
+}
+
+/// Asserts that `got` contains every value specified in `exp`, per [`cmp_expected`]'s subset
+/// semantics. Panics with a pretty-printed diff of the first mismatch if it does not.
+///
+/// ```rust
+/// use serde_json::json;
+/// use cmp_json::assert_json_include;
+///
+/// assert_json_include!(json!({"some": "value", "another": "field"}), json!({"some": "value"}));
+/// ```
+#[macro_export]
+macro_rules! assert_json_include {
+    ($got:expr, $exp:expr) => {
+        if let Some(mismatch) = $crate::find_mismatch(&$got, &$exp) {
+            panic!("{}", $crate::format_mismatch(&mismatch));
+        }
+    };
+}
+
+/// Asserts that `got` and `exp` are structurally identical: unlike [`assert_json_include!`],
+/// `got` may not contain keys absent from `exp`. Panics with a pretty-printed diff of the first
+/// mismatch if they are not equal.
+///
+/// ```rust
+/// use serde_json::json;
+/// use cmp_json::assert_json_eq;
+///
+/// assert_json_eq!(json!({"some": "value"}), json!({"some": "value"}));
+/// ```
+#[macro_export]
+macro_rules! assert_json_eq {
+    ($got:expr, $exp:expr) => {
+        {
+            let opts = $crate::Options { exact: true, ..$crate::Options::default() };
+            if let Some(mismatch) = $crate::find_mismatch_with(&$got, &$exp, &opts) {
+                panic!("{}", $crate::format_mismatch(&mismatch));
+            }
+        }
+    };
+}
+
+/// Walks `got` and `exp` the same way `cmp_expected` does, but on the first disagreement
+/// returns a [`Mismatch`] describing where and why the comparison failed, instead of a bare
+/// `bool`. Returns `None` when `got` matches `exp`.
+///
+/// ```rust
+///
+/// use serde_json::json;
+/// use cmp_json::{find_mismatch, MismatchKind};
+///
+/// let exp = json!{{
+///     "baz": [{"first": true}, {"second": 2}]
+/// }};
+/// let got = json!{{
+///     "baz": [{"first": true}, {"second": 3}]
+/// }};
+///
+/// let mismatch = find_mismatch(&got, &exp).unwrap();
+/// assert_eq!(mismatch.path, "/baz/1/second");
+/// assert_eq!(mismatch.kind, MismatchKind::ValueDiffers);
+/// ```
+///
+pub fn find_mismatch<'a>(got: &'a Value, exp: &'a Value) -> Option<Mismatch<'a>> {
+    find_mismatch_with(got, exp, &Options::default())
+}
+
+/// Like [`find_mismatch`], but matched according to `opts` instead of the default behavior.
+pub fn find_mismatch_with<'a>(got: &'a Value, exp: &'a Value, opts: &Options) -> Option<Mismatch<'a>> {
+    find_mismatch_at(got, exp, opts, String::new())
+}
+
+fn find_mismatch_at<'a>(got: &'a Value, exp: &'a Value, opts: &Options, path: String) -> Option<Mismatch<'a>> {
+    if opts.wildcards {
+        if let Value::String(e_s) = exp {
+            if e_s == "{...}" {
+                return None
+            }
+        }
+    }
     match exp {
         Value::Array(e_arr) => {
             match got.as_array() {
                 Some(g_arr) => {
                     if e_arr.len() != g_arr.len() {
-                        return false
+                        return Some(Mismatch { path, kind: MismatchKind::ArrayLengthMismatch, exp, got })
+                    }
+                    if opts.ignore_array_order {
+                        if arrays_match_unordered(e_arr, g_arr, opts) {
+                            None
+                        } else {
+                            Some(Mismatch { path, kind: MismatchKind::ValueDiffers, exp, got })
+                        }
+                    } else {
+                        e_arr.iter().zip(g_arr.iter()).enumerate().find_map(
+                            |(i, (e, g))| find_mismatch_at(g, e, opts, format!("{}/{}", path, i))
+                        )
                     }
-                    e_arr.iter().zip(
-                        g_arr.iter()
-                    ).all(|(e, g)| cmp_expected(g, e))
                 }
-                None => false
-            } 
+                None => Some(Mismatch { path, kind: MismatchKind::TypeMismatch, exp, got })
+            }
         }
         Value::Object(e_obj) => {
+            if let Some((op, operand)) = as_operator(e_obj) {
+                return if operator_matches(op, operand, got, opts, &path) {
+                    None
+                } else {
+                    Some(Mismatch { path, kind: MismatchKind::ValueDiffers, exp: operand, got })
+                }
+            }
             match got.as_object() {
                 Some(g_obj) => {
                     // We only iterate through expected; if there are values in got that do
-                    // not match expected, that is fine
-                    e_obj.iter().all(
-                        |(k, e_val)| 
+                    // not match expected, that is fine (unless opts.exact is set)
+                    e_obj.iter().find_map(
+                        |(k, e_val)|
                         match g_obj.get(k) {
-                            Some(g_val) => cmp_expected(g_val, e_val),
-                            None => false
+                            Some(g_val) => find_mismatch_at(g_val, e_val, opts, format!("{}/{}", path, encode_token(k))),
+                            None => Some(Mismatch { path: format!("{}/{}", path, encode_token(k)), kind: MismatchKind::MissingKey, exp: e_val, got })
                         }
-                    )
+                    ).or_else(|| {
+                        if !opts.exact {
+                            return None
+                        }
+                        let (k, g_val) = g_obj.iter().find(|(k, _)| !e_obj.contains_key(*k))?;
+                        Some(Mismatch { path: format!("{}/{}", path, encode_token(k)), kind: MismatchKind::ExtraKey, exp, got: g_val })
+                    })
                 }
-                None => false
+                None => Some(Mismatch { path, kind: MismatchKind::TypeMismatch, exp, got })
+            }
+        }
+        Value::String(e_s) if opts.wildcards && e_s.contains("[..]") => {
+            match got.as_str() {
+                Some(g_s) if wildcard_str_matches(e_s, g_s) => None,
+                Some(_) => Some(Mismatch { path, kind: MismatchKind::ValueDiffers, exp, got }),
+                None => Some(Mismatch { path, kind: MismatchKind::TypeMismatch, exp, got })
+            }
+        }
+        Value::Number(e_n) => {
+            match got {
+                Value::Number(g_n) if numbers_equal(e_n, g_n) => None,
+                Value::Number(_) => Some(Mismatch { path, kind: MismatchKind::ValueDiffers, exp, got }),
+                _ => Some(Mismatch { path, kind: MismatchKind::TypeMismatch, exp, got })
+            }
+        }
+        _ => {
+            if got == exp {
+                None
+            } else {
+                Some(Mismatch { path, kind: MismatchKind::ValueDiffers, exp, got })
             }
         }
-        _ => got == exp
     }
 }
 
@@ -285,8 +641,282 @@ mod tests {
             ],
         }};
         assert_eq!(
-            cmp_expected(&got, &exp), 
+            cmp_expected(&got, &exp),
             false
         );
     }
+
+    #[test]
+    fn find_mismatch_none_when_equal() {
+        let got = json!{{"foo": "bar"}};
+        let exp = json!{{"foo": "bar"}};
+        assert_eq!(find_mismatch(&got, &exp), None);
+    }
+
+    #[test]
+    fn find_mismatch_value_differs_has_path() {
+        let got = json!{{"baz": [{"first": true}, {"second": 3}]}};
+        let exp = json!{{"baz": [{"first": true}, {"second": 2}]}};
+        let mismatch = find_mismatch(&got, &exp).unwrap();
+        assert_eq!(mismatch.path, "/baz/1/second");
+        assert_eq!(mismatch.kind, MismatchKind::ValueDiffers);
+    }
+
+    #[test]
+    fn find_mismatch_missing_key() {
+        let got = json!{{"foo": "bar"}};
+        let exp = json!{{"foo": "bar", "baz": 1}};
+        let mismatch = find_mismatch(&got, &exp).unwrap();
+        assert_eq!(mismatch.path, "/baz");
+        assert_eq!(mismatch.kind, MismatchKind::MissingKey);
+    }
+
+    #[test]
+    fn find_mismatch_type_mismatch() {
+        let got = json!("not an object");
+        let exp = json!{{"foo": "bar"}};
+        let mismatch = find_mismatch(&got, &exp).unwrap();
+        assert_eq!(mismatch.path, "");
+        assert_eq!(mismatch.kind, MismatchKind::TypeMismatch);
+    }
+
+    #[test]
+    fn find_mismatch_array_length_mismatch() {
+        let got = json!([1, 2]);
+        let exp = json!([1, 2, 3]);
+        let mismatch = find_mismatch(&got, &exp).unwrap();
+        assert_eq!(mismatch.path, "");
+        assert_eq!(mismatch.kind, MismatchKind::ArrayLengthMismatch);
+    }
+
+    #[test]
+    fn find_mismatch_escapes_pointer_tokens() {
+        let got = json!({});
+        let exp = json!{{"a/b~c": 1}};
+        let mismatch = find_mismatch(&got, &exp).unwrap();
+        assert_eq!(mismatch.path, "/a~1b~0c");
+        assert_eq!(mismatch.kind, MismatchKind::MissingKey);
+    }
+
+    #[test]
+    fn wildcards_disabled_by_default() {
+        let got = json!{{"id": "id-9f3a"}};
+        let exp = json!{{"id": "id-[..]"}};
+        assert_eq!(cmp_expected(&got, &exp), false);
+    }
+
+    #[test]
+    fn wildcard_any_value() {
+        let got = json!{{"id": {"anything": "at all"}}};
+        let exp = json!{{"id": "{...}"}};
+        let opts = Options { wildcards: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn wildcard_string_fill() {
+        let got = json!("id-9f3a");
+        let exp = json!("id-[..]");
+        let opts = Options { wildcards: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn wildcard_string_fill_multiple() {
+        let got = json!("2024-01-02T03:04:05Z");
+        let exp = json!("[..]-01-[..]T[..]:04:[..]");
+        let opts = Options { wildcards: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn wildcard_string_fill_mismatch() {
+        let got = json!("name-9f3a");
+        let exp = json!("id-[..]");
+        let opts = Options { wildcards: true, ..Options::default() };
+        assert_eq!(cmp_expected_with(&got, &exp, &opts), false);
+    }
+
+    #[test]
+    fn wildcard_string_fill_wrong_type() {
+        let got = json!(1234);
+        let exp = json!("id-[..]");
+        let opts = Options { wildcards: true, ..Options::default() };
+        assert_eq!(cmp_expected_with(&got, &exp, &opts), false);
+    }
+
+    #[test]
+    fn number_int_equals_float() {
+        let got = json!(1.0);
+        let exp = json!(1);
+        assert!(cmp_expected(&got, &exp));
+    }
+
+    #[test]
+    fn number_negative_zero_equals_zero() {
+        let got = json!(-0.0);
+        let exp = json!(0);
+        assert!(cmp_expected(&got, &exp));
+    }
+
+    #[test]
+    fn number_large_u64_equals_itself() {
+        let got = json!(u64::MAX);
+        let exp = json!(u64::MAX);
+        assert!(cmp_expected(&got, &exp));
+    }
+
+    #[test]
+    fn number_large_u64_not_truncated_by_f64() {
+        let got = json!(u64::MAX);
+        let exp = json!(u64::MAX - 1);
+        assert_eq!(cmp_expected(&got, &exp), false);
+    }
+
+    #[test]
+    fn unordered_arrays_disabled_by_default() {
+        let got = json!([2, 1]);
+        let exp = json!([1, 2]);
+        assert_eq!(cmp_expected(&got, &exp), false);
+    }
+
+    #[test]
+    fn unordered_arrays_match_out_of_order() {
+        let got = json!([2, 1, 3]);
+        let exp = json!([1, 2, 3]);
+        let opts = Options { ignore_array_order: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn unordered_arrays_require_distinct_matches() {
+        // Both expected elements can only partial-match the same single got element, so no
+        // full matching exists even though neither pair individually fails.
+        let got = json!([{"x": 1, "y": 2}, {"z": 3}]);
+        let exp = json!([{"x": 1}, {"y": 2}]);
+        let opts = Options { ignore_array_order: true, ..Options::default() };
+        assert_eq!(cmp_expected_with(&got, &exp, &opts), false);
+    }
+
+    #[test]
+    fn unordered_arrays_find_augmenting_match() {
+        // exp[0] can match either got element, but exp[1] can only match got[0]; a full
+        // matching exists only if exp[0] yields got[0] to exp[1] and takes got[1] instead,
+        // which requires an augmenting path rather than a first-match-wins scan.
+        let got = json!([{"x": 1, "y": 2}, {"x": 1}]);
+        let exp = json!([{"x": 1}, {"x": 1, "y": 2}]);
+        let opts = Options { ignore_array_order: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn unordered_arrays_still_enforce_length() {
+        let got = json!([1, 2]);
+        let exp = json!([1, 2, 3]);
+        let opts = Options { ignore_array_order: true, ..Options::default() };
+        let mismatch = find_mismatch_with(&got, &exp, &opts).unwrap();
+        assert_eq!(mismatch.kind, MismatchKind::ArrayLengthMismatch);
+    }
+
+    #[test]
+    fn operator_gt() {
+        assert!(cmp_expected(&json!(101), &json!({"$gt": 100})));
+        assert_eq!(cmp_expected(&json!(100), &json!({"$gt": 100})), false);
+    }
+
+    #[test]
+    fn operator_gte() {
+        assert!(cmp_expected(&json!(100), &json!({"$gte": 100})));
+        assert_eq!(cmp_expected(&json!(99), &json!({"$gte": 100})), false);
+    }
+
+    #[test]
+    fn operator_lt() {
+        assert!(cmp_expected(&json!(99), &json!({"$lt": 100})));
+        assert_eq!(cmp_expected(&json!(100), &json!({"$lt": 100})), false);
+    }
+
+    #[test]
+    fn operator_lte() {
+        assert!(cmp_expected(&json!(100), &json!({"$lte": 100})));
+        assert_eq!(cmp_expected(&json!(101), &json!({"$lte": 100})), false);
+    }
+
+    #[test]
+    fn operator_ne_scalar() {
+        assert!(cmp_expected(&json!("b"), &json!({"$ne": "a"})));
+        assert_eq!(cmp_expected(&json!("a"), &json!({"$ne": "a"})), false);
+    }
+
+    #[test]
+    fn operator_ne_negates_recursive_compare() {
+        let got = json!{{"foo": "bar"}};
+        let exp = json!{{"$ne": {"foo": "baz"}}};
+        assert!(cmp_expected(&got, &exp));
+
+        let exp_matches = json!{{"$ne": {"foo": "bar"}}};
+        assert_eq!(cmp_expected(&got, &exp_matches), false);
+    }
+
+    #[test]
+    fn operator_gt_on_strings() {
+        assert!(cmp_expected(&json!("banana"), &json!({"$gt": "apple"})));
+        assert_eq!(cmp_expected(&json!("apple"), &json!({"$gt": "banana"})), false);
+    }
+
+    #[test]
+    fn operator_nested_in_object() {
+        let got = json!{{"count": 5}};
+        let exp = json!{{"count": {"$gte": 1}}};
+        assert!(cmp_expected(&got, &exp));
+    }
+
+    #[test]
+    fn non_operator_objects_still_compared_as_literals() {
+        // An object with more than one key, even if one of them looks like an operator,
+        // is matched as a literal object, not treated as an operator.
+        let got = json!{{"$gt": 100, "extra": true}};
+        let exp = json!{{"$gt": 100, "extra": true}};
+        assert!(cmp_expected(&got, &exp));
+    }
+
+    #[test]
+    fn exact_option_rejects_extra_keys() {
+        let got = json!{{"foo": "bar", "extra": true}};
+        let exp = json!{{"foo": "bar"}};
+        let opts = Options { exact: true, ..Options::default() };
+        let mismatch = find_mismatch_with(&got, &exp, &opts).unwrap();
+        assert_eq!(mismatch.path, "/extra");
+        assert_eq!(mismatch.kind, MismatchKind::ExtraKey);
+    }
+
+    #[test]
+    fn exact_option_accepts_identical_objects() {
+        let got = json!{{"foo": "bar"}};
+        let exp = json!{{"foo": "bar"}};
+        let opts = Options { exact: true, ..Options::default() };
+        assert!(cmp_expected_with(&got, &exp, &opts));
+    }
+
+    #[test]
+    fn assert_json_include_passes_with_extra_keys() {
+        assert_json_include!(json!({"some": "value", "another": "field"}), json!({"some": "value"}));
+    }
+
+    #[test]
+    #[should_panic(expected = "json mismatch at `/some`")]
+    fn assert_json_include_panics_on_mismatch() {
+        assert_json_include!(json!({"some": "other value"}), json!({"some": "value"}));
+    }
+
+    #[test]
+    fn assert_json_eq_passes_on_identical_values() {
+        assert_json_eq!(json!({"some": "value"}), json!({"some": "value"}));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected key")]
+    fn assert_json_eq_panics_on_extra_keys() {
+        assert_json_eq!(json!({"some": "value", "another": "field"}), json!({"some": "value"}));
+    }
 }